@@ -0,0 +1,146 @@
+//! Bearer-token authentication for mutating requests.
+//!
+//! Validates the `Authorization` header against a configurable set of tokens
+//! and returns an authenticated [`Principal`] before the request reaches a
+//! write handler, so handlers never parse headers themselves. The principal is
+//! not attached to the request; handlers receive only the request and its
+//! path params.
+
+use std::collections::HashSet;
+use std::env;
+
+use crate::http::Request;
+
+/// The name of the environment variable holding the accepted bearer tokens,
+/// as a comma-separated list.
+pub const AUTH_TOKENS_ENV_VAR: &str = "AUTH_TOKENS";
+
+/// The authenticated caller, returned by [`TokenAuth::authenticate`] once its
+/// bearer token checks out.
+#[derive(Debug, Clone)]
+pub struct Principal {
+    pub token: String,
+}
+
+/// Why a request failed bearer-token authentication.
+#[derive(Debug)]
+pub enum AuthError {
+    /// No `Authorization` header was present.
+    Missing,
+    /// The header wasn't a `Bearer <token>` value.
+    Malformed,
+    /// The token didn't match any configured token.
+    Invalid,
+}
+
+impl AuthError {
+    pub fn message(&self) -> &'static str {
+        match self {
+            AuthError::Missing => "Missing Authorization header",
+            AuthError::Malformed => "Authorization header must be a Bearer token",
+            AuthError::Invalid => "Invalid bearer token",
+        }
+    }
+}
+
+/// Validates bearer tokens against a configurable set of accepted values.
+pub struct TokenAuth {
+    valid_tokens: HashSet<String>,
+}
+
+impl TokenAuth {
+    pub fn new(valid_tokens: impl IntoIterator<Item = String>) -> Self {
+        TokenAuth {
+            valid_tokens: valid_tokens.into_iter().collect(),
+        }
+    }
+
+    /// Builds a `TokenAuth` from the comma-separated [`AUTH_TOKENS_ENV_VAR`]
+    /// environment variable. Empty or missing entries are ignored, so an unset
+    /// variable means no token will ever authenticate.
+    pub fn from_env() -> Self {
+        let tokens = env::var(AUTH_TOKENS_ENV_VAR).unwrap_or_default();
+        Self::new(
+            tokens
+                .split(',')
+                .map(str::trim)
+                .filter(|token| !token.is_empty())
+                .map(str::to_string),
+        )
+    }
+
+    /// Extracts and validates the bearer token from `request`'s `Authorization`
+    /// header.
+    pub fn authenticate(&self, request: &Request) -> Result<Principal, AuthError> {
+        let header = request.headers.get("authorization").ok_or(AuthError::Missing)?;
+
+        let token = header.strip_prefix("Bearer ").ok_or(AuthError::Malformed)?.trim();
+
+        if token.is_empty() || !self.valid_tokens.contains(token) {
+            return Err(AuthError::Invalid);
+        }
+
+        Ok(Principal {
+            token: token.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn request_with_header(header: Option<&str>) -> Request {
+        let mut headers = HashMap::new();
+        if let Some(value) = header {
+            headers.insert("authorization".to_string(), value.to_string());
+        }
+
+        Request {
+            method: "POST".to_string(),
+            path: "/api/movies".to_string(),
+            query: HashMap::new(),
+            headers,
+            body: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn missing_authorization_header_is_rejected() {
+        let auth = TokenAuth::new(["secret".to_string()]);
+
+        let err = auth.authenticate(&request_with_header(None)).unwrap_err();
+
+        assert!(matches!(err, AuthError::Missing));
+    }
+
+    #[test]
+    fn non_bearer_header_is_rejected_as_malformed() {
+        let auth = TokenAuth::new(["secret".to_string()]);
+
+        let err = auth.authenticate(&request_with_header(Some("Basic secret"))).unwrap_err();
+
+        assert!(matches!(err, AuthError::Malformed));
+    }
+
+    #[test]
+    fn unrecognized_token_is_rejected_as_invalid() {
+        let auth = TokenAuth::new(["secret".to_string()]);
+
+        let err = auth
+            .authenticate(&request_with_header(Some("Bearer wrong-token")))
+            .unwrap_err();
+
+        assert!(matches!(err, AuthError::Invalid));
+    }
+
+    #[test]
+    fn valid_token_authenticates() {
+        let auth = TokenAuth::new(["secret".to_string()]);
+
+        let principal = auth.authenticate(&request_with_header(Some("Bearer secret"))).unwrap();
+
+        assert_eq!(principal.token, "secret");
+    }
+}
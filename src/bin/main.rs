@@ -1,12 +1,19 @@
 use std::io::prelude::*;
 use std::net::TcpListener;
 use std::net::TcpStream;
-
-use firebase_rs::Firebase;
+use std::sync::Arc;
+use std::time::Instant;
+
+use rust_http_server::auth::TokenAuth;
+use rust_http_server::http::{
+    read_request, ApiResponse, Dispatch, Handler, RenderedResponse, Request, RequestError, Router,
+    DEFAULT_MAX_BODY_SIZE,
+};
+use rust_http_server::metrics::Metrics;
+use rust_http_server::storage::{ErrorKind, FirebaseStorage, MemoryStorage, Storage, StorageError};
 use rust_http_server::ThreadPool;
-use serde::{Deserialize, Serialize};
-use serde_json::json;
-use tokio::runtime::Runtime;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 struct Movie {
@@ -32,547 +39,288 @@ struct Review {
 const FIREBASE_URL: &str =
     "https://rust-movie-project-default-rtdb.europe-west1.firebasedatabase.app/";
 
+/// Name of the environment variable selecting the storage backend. Set to
+/// `memory` to run against an in-memory store instead of Firebase, e.g. for
+/// local development without network access. Defaults to `firebase`.
+const STORAGE_BACKEND_ENV_VAR: &str = "STORAGE_BACKEND";
+
 #[tokio::main]
 async fn main() {
     let listener = TcpListener::bind("0.0.0.0:8080").unwrap();
 
-    let pool = ThreadPool::new(4);
+    let storage = build_storage();
+
+    let metrics = Arc::new(Metrics::new());
+    let pool = Arc::new(ThreadPool::new(4));
+    let auth = Arc::new(TokenAuth::from_env());
+    let router = Arc::new(build_router(storage, Arc::clone(&metrics), Arc::clone(&pool), auth));
+
+    let shutdown_pool = Arc::clone(&pool);
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            println!("Received Ctrl-C, draining in-flight connections before exiting");
+            shutdown_pool.shutdown();
+            // listener.incoming() blocks on accept() with no cancellation hook, so
+            // there's no way to make the accept loop below notice shutdown on its
+            // own; exit directly once the pool is drained instead of leaving the
+            // loop running (and silently dropping every job `execute` now rejects).
+            std::process::exit(0);
+        }
+    });
 
     for stream in listener.incoming() {
         let stream = stream.unwrap();
+        let router = Arc::clone(&router);
+        let metrics = Arc::clone(&metrics);
 
-        pool.execute(|| {
-            handle_connection(stream);
+        pool.execute(move || {
+            handle_connection(stream, router, metrics);
         });
     }
 }
 
-fn handle_connection(mut stream: TcpStream) {
-    let mut buffer = [0; 1024];
-    stream.read(&mut buffer).unwrap();
-    let request = String::from_utf8_lossy(&buffer[..]);
-
-    let (method, path) = parse_request_line(&request);
-
-    let response = Runtime::new().unwrap().block_on(async {
-        match (method, path) {
-            ("GET", "/api/movies") => handle_get_movies().await,
-            ("POST", "/api/movies") => handle_post_movies(&request).await,
-            ("PUT", "/api/movies") => handle_put_movies(&request).await,
-            ("DELETE", "/api/movies") => handle_delete_movies(&request).await,
-            ("GET", "/api/actors") => handle_get_actors().await,
-            ("POST", "/api/actors") => handle_post_actors(&request).await,
-            ("PUT", "/api/actors") => handle_put_actors(&request).await,
-            ("DELETE", "/api/actors") => handle_delete_actors(&request).await,
-            ("GET", "/api/reviews") => handle_get_reviews().await,
-            ("POST", "/api/reviews") => handle_post_reviews(&request).await,
-            ("PUT", "/api/reviews") => handle_put_reviews(&request).await,
-            ("DELETE", "/api/reviews") => handle_delete_reviews(&request).await,
-            _ => handle_404(),
-        }
-    });
-
-    stream.write(response.as_bytes()).unwrap();
-    stream.flush().unwrap();
-}
-
-fn parse_request_line(request: &str) -> (&str, &str) {
-    let mut lines = request.lines();
-    if let Some(request_line) = lines.next() {
-        let parts: Vec<&str> = request_line.split_whitespace().collect();
-        if parts.len() >= 2 {
-            return (parts[0], parts[1]);
-        }
+/// Selects a [`Storage`] backend based on the [`STORAGE_BACKEND_ENV_VAR`]
+/// environment variable: `memory` for [`MemoryStorage`], anything else
+/// (including unset) for [`FirebaseStorage`].
+fn build_storage() -> Arc<dyn Storage> {
+    match std::env::var(STORAGE_BACKEND_ENV_VAR).as_deref() {
+        Ok("memory") => Arc::new(MemoryStorage::new()),
+        _ => Arc::new(FirebaseStorage::new(FIREBASE_URL).expect("failed to configure Firebase storage")),
     }
-    ("", "")
 }
 
-async fn handle_get_movies() -> String {
-    let firebase = Firebase::new(FIREBASE_URL).unwrap();
-    let movies_result = firebase.at("movies").get::<serde_json::Value>().await;
-
-    let status_line;
-    let contents;
-
-    match movies_result {
-        Ok(movies) => {
-            if movies.is_null() {
-                status_line = "HTTP/1.0 200 OK";
-                contents = "[]".to_string(); // Return an empty list if no movies are found
-            } else {
-                status_line = "HTTP/1.0 200 OK";
-                contents = movies.to_string();
-            }
-        }
-        Err(_) => {
-            status_line = "HTTP/1.0 500 INTERNAL SERVER ERROR";
-            contents = "Failed to retrieve movies".to_string();
-        }
-    }
-
-    format!(
-        "{}\r\nContent-Length: {}\r\n\r\n{}",
-        status_line,
-        contents.len(),
-        contents
-    )
+/// Registers the RESTful routes for each collection resource plus `GET /metrics`.
+fn build_router(
+    storage: Arc<dyn Storage>,
+    metrics: Arc<Metrics>,
+    pool: Arc<ThreadPool>,
+    auth: Arc<TokenAuth>,
+) -> Router {
+    let mut router = Router::new();
+    register_collection::<Movie>(&mut router, &storage, &auth, "movies");
+    register_collection::<Actor>(&mut router, &storage, &auth, "actors");
+    register_collection::<Review>(&mut router, &storage, &auth, "reviews");
+
+    router.route(
+        "GET",
+        "/metrics",
+        Box::new(move |_req, _params| {
+            let metrics = Arc::clone(&metrics);
+            let pool = Arc::clone(&pool);
+            Box::pin(async move { render_metrics(&metrics, &pool) })
+        }),
+    );
+
+    router
 }
 
-async fn handle_post_movies(request: &str) -> String {
-    println!("Received POST request: {}", request);
-
-    let firebase = Firebase::new(FIREBASE_URL).unwrap();
-
-    if let Some(body) = request.split("\r\n\r\n").nth(1) {
-        let sanitized_body = body.replace('\0', "").trim().to_string();
-
-        match serde_json::from_str::<Movie>(&sanitized_body) {
-            Ok(movie) => {
-                let movie_json = json!(movie);
-                let path = format!("movies/");
-                match firebase.at(&path).set(&movie_json).await {
-                    Ok(_) => {
-                        let status_line = "HTTP/1.0 201 CREATED";
-                        let contents = "Movie created";
-                        return format!(
-                            "{}\r\nContent-Length: {}\r\n\r\n{}",
-                            status_line,
-                            contents.len(),
-                            contents
-                        );
-                    }
-                    Err(e) => {
-                        println!("Error setting movie in Firebase: {:?}", e);
-                    }
-                }
-            }
-            Err(e) => {
-                println!("Failed to parse movie JSON: {:?}", e);
-            }
-        }
-    } else {
-        println!("Failed to extract body from request");
-    }
+fn render_metrics(metrics: &Metrics, pool: &ThreadPool) -> RenderedResponse {
+    let body = metrics.render(pool);
+    let text = format!(
+        "HTTP/1.0 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\n\r\n{}",
+        body.len(),
+        body
+    );
+    RenderedResponse { status: 200, text }
+}
 
-    handle_400()
+/// Registers the standard `list`/`create`/`get`/`update`/`delete` routes for one
+/// collection, e.g. `/api/movies` and `/api/movies/{id}`. Mutating routes
+/// (`POST`/`PUT`/`DELETE`) are gated behind bearer-token auth; `GET`s stay public.
+fn register_collection<T>(
+    router: &mut Router,
+    storage: &Arc<dyn Storage>,
+    auth: &Arc<TokenAuth>,
+    collection: &'static str,
+) where
+    T: Serialize + DeserializeOwned + Send + 'static,
+{
+    let base = format!("/api/{}", collection);
+    let item = format!("/api/{}/{{id}}", collection);
+
+    let s = Arc::clone(storage);
+    router.route(
+        "GET",
+        &base,
+        Box::new(move |_req, _params| {
+            let s = Arc::clone(&s);
+            Box::pin(async move { handle_list(&s, collection).await })
+        }),
+    );
+
+    let s = Arc::clone(storage);
+    router.route(
+        "POST",
+        &base,
+        require_auth(
+            auth,
+            Box::new(move |req, _params| {
+                let s = Arc::clone(&s);
+                Box::pin(async move { handle_create::<T>(&s, collection, &req).await })
+            }),
+        ),
+    );
+
+    let s = Arc::clone(storage);
+    router.route(
+        "GET",
+        &item,
+        Box::new(move |_req, params| {
+            let s = Arc::clone(&s);
+            Box::pin(async move { handle_get(&s, collection, &params["id"]).await })
+        }),
+    );
+
+    let s = Arc::clone(storage);
+    router.route(
+        "PUT",
+        &item,
+        require_auth(
+            auth,
+            Box::new(move |req, params| {
+                let s = Arc::clone(&s);
+                Box::pin(async move { handle_update(&s, collection, &params["id"], &req).await })
+            }),
+        ),
+    );
+
+    let s = Arc::clone(storage);
+    router.route(
+        "DELETE",
+        &item,
+        require_auth(
+            auth,
+            Box::new(move |_req, params| {
+                let s = Arc::clone(&s);
+                Box::pin(async move { handle_delete(&s, collection, &params["id"]).await })
+            }),
+        ),
+    );
 }
 
-async fn handle_put_movies(request: &str) -> String {
-    println!("Received PUT request: {}", request);
-
-    let firebase = Firebase::new(FIREBASE_URL).unwrap();
-    let body = request
-        .split("\r\n\r\n")
-        .nth(1)
-        .unwrap_or("")
-        .replace('\0', "")
-        .trim()
-        .to_string();
-
-    match serde_json::from_str::<serde_json::Value>(&body) {
-        Ok(movie_update) => {
-            if let Some(id) = movie_update.get("id").and_then(|id| id.as_str()) {
-                let path = format!("movies/{}", id);
-                match firebase.at(&path).update(&movie_update).await {
-                    Ok(_) => {
-                        let status_line = "HTTP/1.0 200 OK";
-                        let contents = "Movie updated";
-                        return format!(
-                            "{}\r\nContent-Length: {}\r\n\r\n{}",
-                            status_line,
-                            contents.len(),
-                            contents
-                        );
-                    }
-                    Err(e) => {
-                        println!("Error updating movie in Firebase: {:?}", e);
-                    }
-                }
-            } else {
-                println!("Movie ID not provided in the request");
-            }
-        }
+/// Wraps `inner` so it only runs once `request` carries a valid bearer token;
+/// otherwise responds `401` with a `WWW-Authenticate` header.
+fn require_auth(auth: &Arc<TokenAuth>, inner: Handler) -> Handler {
+    let auth = Arc::clone(auth);
+    Box::new(move |request, params| match auth.authenticate(&request) {
+        Ok(_principal) => inner(request, params),
         Err(e) => {
-            println!("Failed to parse movie JSON: {:?}", e);
+            let message = e.message();
+            Box::pin(async move {
+                ApiResponse::<Value>::failure(401, message).render_with_headers(&[("WWW-Authenticate", "Bearer")])
+            })
         }
-    }
-
-    handle_400()
+    })
 }
 
-async fn handle_delete_movies(request: &str) -> String {
-    println!("Received DELETE request: {}", request);
-
-    let firebase = Firebase::new(FIREBASE_URL).unwrap();
-    let body = request
-        .split("\r\n\r\n")
-        .nth(1)
-        .unwrap_or("")
-        .replace('\0', "")
-        .trim()
-        .to_string();
-
-    match serde_json::from_str::<serde_json::Value>(&body) {
-        Ok(movie) => {
-            if let Some(id) = movie.get("id").and_then(|id| id.as_str()) {
-                let path = format!("movies/{}", id);
-                match firebase.at(&path).delete().await {
-                    Ok(_) => {
-                        let status_line = "HTTP/1.0 200 OK";
-                        let contents = "Movie deleted";
-                        return format!(
-                            "{}\r\nContent-Length: {}\r\n\r\n{}",
-                            status_line,
-                            contents.len(),
-                            contents
-                        );
-                    }
-                    Err(e) => {
-                        println!("Error deleting movie in Firebase: {:?}", e);
-                    }
-                }
-            } else {
-                println!("Movie ID not provided in the request");
-            }
+fn handle_connection(mut stream: TcpStream, router: Arc<Router>, metrics: Arc<Metrics>) {
+    let request = match read_request(&mut stream, DEFAULT_MAX_BODY_SIZE) {
+        Ok(request) => request,
+        Err(RequestError::TooLarge(len)) => {
+            println!("Rejecting request with {}-byte body: exceeds the configured limit", len);
+            let response = ApiResponse::<Value>::failure(413, "Payload Too Large").render();
+            let _ = stream.write(response.text.as_bytes());
+            let _ = stream.flush();
+            return;
         }
         Err(e) => {
-            println!("Failed to parse movie JSON: {:?}", e);
+            println!("Failed to read request: {}", e);
+            let response = ApiResponse::<Value>::failure(400, e.to_string()).render();
+            let _ = stream.write(response.text.as_bytes());
+            let _ = stream.flush();
+            return;
         }
-    }
-
-    handle_400()
-}
-
-async fn handle_get_actors() -> String {
-    let firebase = Firebase::new(FIREBASE_URL).unwrap();
-    let actors_result = firebase.at("actors").get::<serde_json::Value>().await;
-
-    let status_line;
-    let contents;
-
-    match actors_result {
-        Ok(actors) => {
-            if actors.is_null() {
-                status_line = "HTTP/1.0 200 OK";
-                contents = "[]".to_string(); // Return an empty list if no actors are found
-            } else {
-                status_line = "HTTP/1.0 200 OK";
-                contents = actors.to_string();
+    };
+
+    let method = request.method.clone();
+    let started_at = Instant::now();
+
+    let (route, response) = tokio::runtime::Runtime::new().unwrap().block_on(async {
+        match router.dispatch(&request.method, &request.path) {
+            Dispatch::Matched { handler, params, route } => (route.to_string(), handler(request, params).await),
+            Dispatch::MethodNotAllowed { allowed, route } => {
+                let allow = allowed.join(", ");
+                let response = ApiResponse::<Value>::failure(405, "Method Not Allowed")
+                    .render_with_headers(&[("Allow", &allow)]);
+                (route.to_string(), response)
             }
+            Dispatch::NotFound => ("unmatched".to_string(), ApiResponse::<Value>::failure(404, "Not Found").render()),
         }
-        Err(_) => {
-            status_line = "HTTP/1.0 500 INTERNAL SERVER ERROR";
-            contents = "Failed to retrieve actors".to_string();
-        }
-    }
+    });
 
-    format!(
-        "{}\r\nContent-Length: {}\r\n\r\n{}",
-        status_line,
-        contents.len(),
-        contents
-    )
-}
+    metrics.record_request(&method, &route, response.status, started_at.elapsed());
 
-async fn handle_post_actors(request: &str) -> String {
-    println!("Received POST request: {}", request);
-
-    let firebase = Firebase::new(FIREBASE_URL).unwrap();
-
-    if let Some(body) = request.split("\r\n\r\n").nth(1) {
-        let sanitized_body = body.replace('\0', "").trim().to_string();
-
-        match serde_json::from_str::<Actor>(&sanitized_body) {
-            Ok(actor) => {
-                let actor_json = json!(actor);
-                let path = format!("actors/");
-                match firebase.at(&path).set(&actor_json).await {
-                    Ok(_) => {
-                        let status_line = "HTTP/1.0 201 CREATED";
-                        let contents = "Actor created";
-                        return format!(
-                            "{}\r\nContent-Length: {}\r\n\r\n{}",
-                            status_line,
-                            contents.len(),
-                            contents
-                        );
-                    }
-                    Err(e) => {
-                        println!("Error setting actor in Firebase: {:?}", e);
-                    }
-                }
-            }
-            Err(e) => {
-                println!("Failed to parse actor JSON: {:?}", e);
-            }
-        }
-    } else {
-        println!("Failed to extract body from request");
-    }
-
-    handle_400()
+    stream.write(response.text.as_bytes()).unwrap();
+    stream.flush().unwrap();
 }
 
-async fn handle_put_actors(request: &str) -> String {
-    println!("Received PUT request: {}", request);
-
-    let firebase = Firebase::new(FIREBASE_URL).unwrap();
-    let body = request
-        .split("\r\n\r\n")
-        .nth(1)
-        .unwrap_or("")
-        .replace('\0', "")
-        .trim()
-        .to_string();
-
-    match serde_json::from_str::<serde_json::Value>(&body) {
-        Ok(actor_update) => {
-            if let Some(id) = actor_update.get("id").and_then(|id| id.as_str()) {
-                let path = format!("actors/{}", id);
-                match firebase.at(&path).update(&actor_update).await {
-                    Ok(_) => {
-                        let status_line = "HTTP/1.0 200 OK";
-                        let contents = "Actor updated";
-                        return format!(
-                            "{}\r\nContent-Length: {}\r\n\r\n{}",
-                            status_line,
-                            contents.len(),
-                            contents
-                        );
-                    }
-                    Err(e) => {
-                        println!("Error updating actor in Firebase: {:?}", e);
-                    }
-                }
-            } else {
-                println!("Actor ID not provided in the request");
-            }
-        }
-        Err(e) => {
-            println!("Failed to parse actor JSON: {:?}", e);
-        }
+async fn handle_list(storage: &Arc<dyn Storage>, collection: &str) -> RenderedResponse {
+    match storage.list(collection).await {
+        Ok(items) => ApiResponse::ok(items).render(),
+        Err(e) => storage_error(&e, &format!("Failed to retrieve {}", collection)).render(),
     }
-
-    handle_400()
 }
 
-async fn handle_delete_actors(request: &str) -> String {
-    println!("Received DELETE request: {}", request);
-
-    let firebase = Firebase::new(FIREBASE_URL).unwrap();
-    let body = request
-        .split("\r\n\r\n")
-        .nth(1)
-        .unwrap_or("")
-        .replace('\0', "")
-        .trim()
-        .to_string();
-
-    match serde_json::from_str::<serde_json::Value>(&body) {
-        Ok(actor) => {
-            if let Some(id) = actor.get("id").and_then(|id| id.as_str()) {
-                let path = format!("actors/{}", id);
-                match firebase.at(&path).delete().await {
-                    Ok(_) => {
-                        let status_line = "HTTP/1.0 200 OK";
-                        let contents = "Actor deleted";
-                        return format!(
-                            "{}\r\nContent-Length: {}\r\n\r\n{}",
-                            status_line,
-                            contents.len(),
-                            contents
-                        );
-                    }
-                    Err(e) => {
-                        println!("Error deleting actor in Firebase: {:?}", e);
-                    }
-                }
-            } else {
-                println!("Actor ID not provided in the request");
-            }
-        }
-        Err(e) => {
-            println!("Failed to parse actor JSON: {:?}", e);
-        }
+async fn handle_get(storage: &Arc<dyn Storage>, collection: &str, id: &str) -> RenderedResponse {
+    match storage.get(collection, id).await {
+        Ok(item) => ApiResponse::ok(item).render(),
+        Err(e) => storage_error(&e, &format!("Failed to retrieve {}", singular(collection))).render(),
     }
-
-    handle_400()
 }
 
-async fn handle_get_reviews() -> String {
-    let firebase = Firebase::new(FIREBASE_URL).unwrap();
-    let reviews_result = firebase.at("reviews").get::<serde_json::Value>().await;
-
-    let status_line;
-    let contents;
-
-    match reviews_result {
-        Ok(reviews) => {
-            if reviews.is_null() {
-                status_line = "HTTP/1.0 200 OK";
-                contents = "[]".to_string(); // Return an empty list if no reviews are found
-            } else {
-                status_line = "HTTP/1.0 200 OK";
-                contents = reviews.to_string();
-            }
-        }
-        Err(_) => {
-            status_line = "HTTP/1.0 500 INTERNAL SERVER ERROR";
-            contents = "Failed to retrieve reviews".to_string();
+async fn handle_create<T>(storage: &Arc<dyn Storage>, collection: &str, request: &Request) -> RenderedResponse
+where
+    T: Serialize + DeserializeOwned,
+{
+    println!("Received POST {}", request.path);
+
+    match serde_json::from_slice::<T>(&request.body) {
+        Ok(item) => match storage.create(collection, json!(item)).await {
+            Ok(created) => ApiResponse::created(created).render(),
+            Err(e) => storage_error(&e, &format!("Failed to create {}", singular(collection))).render(),
+        },
+        Err(e) => {
+            println!("Failed to parse {} JSON: {:?}", singular(collection), e);
+            ApiResponse::<Value>::failure(400, format!("invalid {} payload: {}", singular(collection), e)).render()
         }
     }
-
-    format!(
-        "{}\r\nContent-Length: {}\r\n\r\n{}",
-        status_line,
-        contents.len(),
-        contents
-    )
 }
 
-async fn handle_post_reviews(request: &str) -> String {
-    println!("Received POST request: {}", request);
-
-    let firebase = Firebase::new(FIREBASE_URL).unwrap();
-
-    if let Some(body) = request.split("\r\n\r\n").nth(1) {
-        let sanitized_body = body.replace('\0', "").trim().to_string();
-
-        match serde_json::from_str::<Review>(&sanitized_body) {
-            Ok(review) => {
-                let review_json = json!(review);
-                let path = format!("reviews/");
-                match firebase.at(&path).set(&review_json).await {
-                    Ok(_) => {
-                        let status_line = "HTTP/1.0 201 CREATED";
-                        let contents = "Review created";
-                        return format!(
-                            "{}\r\nContent-Length: {}\r\n\r\n{}",
-                            status_line,
-                            contents.len(),
-                            contents
-                        );
-                    }
-                    Err(e) => {
-                        println!("Error setting review in Firebase: {:?}", e);
-                    }
-                }
-            }
-            Err(e) => {
-                println!("Failed to parse review JSON: {:?}", e);
-            }
-        }
-    } else {
-        println!("Failed to extract body from request");
-    }
+async fn handle_update(storage: &Arc<dyn Storage>, collection: &str, id: &str, request: &Request) -> RenderedResponse {
+    println!("Received PUT {}/{}", collection, id);
 
-    handle_400()
-}
-
-async fn handle_put_reviews(request: &str) -> String {
-    println!("Received PUT request: {}", request);
-
-    let firebase = Firebase::new(FIREBASE_URL).unwrap();
-    let body = request
-        .split("\r\n\r\n")
-        .nth(1)
-        .unwrap_or("")
-        .replace('\0', "")
-        .trim()
-        .to_string();
-
-    match serde_json::from_str::<serde_json::Value>(&body) {
-        Ok(review_update) => {
-            if let Some(id) = review_update.get("id").and_then(|id| id.as_str()) {
-                let path = format!("reviews/{}", id);
-                match firebase.at(&path).update(&review_update).await {
-                    Ok(_) => {
-                        let status_line = "HTTP/1.0 200 OK";
-                        let contents = "Review updated";
-                        return format!(
-                            "{}\r\nContent-Length: {}\r\n\r\n{}",
-                            status_line,
-                            contents.len(),
-                            contents
-                        );
-                    }
-                    Err(e) => {
-                        println!("Error updating review in Firebase: {:?}", e);
-                    }
-                }
-            } else {
-                println!("Review ID not provided in the request");
-            }
-        }
+    match serde_json::from_slice::<Value>(&request.body) {
+        Ok(patch) => match storage.update(collection, id, patch).await {
+            Ok(updated) => ApiResponse::ok(updated).render(),
+            Err(e) => storage_error(&e, &format!("Failed to update {}", singular(collection))).render(),
+        },
         Err(e) => {
-            println!("Failed to parse review JSON: {:?}", e);
+            println!("Failed to parse {} JSON: {:?}", singular(collection), e);
+            ApiResponse::<Value>::failure(400, format!("invalid {} payload: {}", singular(collection), e)).render()
         }
     }
-
-    handle_400()
 }
 
-async fn handle_delete_reviews(request: &str) -> String {
-    println!("Received DELETE request: {}", request);
-
-    let firebase = Firebase::new(FIREBASE_URL).unwrap();
-    let body = request
-        .split("\r\n\r\n")
-        .nth(1)
-        .unwrap_or("")
-        .replace('\0', "")
-        .trim()
-        .to_string();
-
-    match serde_json::from_str::<serde_json::Value>(&body) {
-        Ok(review) => {
-            if let Some(id) = review.get("id").and_then(|id| id.as_str()) {
-                let path = format!("reviews/{}", id);
-                match firebase.at(&path).delete().await {
-                    Ok(_) => {
-                        let status_line = "HTTP/1.0 200 OK";
-                        let contents = "Review deleted";
-                        return format!(
-                            "{}\r\nContent-Length: {}\r\n\r\n{}",
-                            status_line,
-                            contents.len(),
-                            contents
-                        );
-                    }
-                    Err(e) => {
-                        println!("Error deleting review in Firebase: {:?}", e);
-                    }
-                }
-            } else {
-                println!("Review ID not provided in the request");
-            }
-        }
-        Err(e) => {
-            println!("Failed to parse review JSON: {:?}", e);
-        }
-    }
+async fn handle_delete(storage: &Arc<dyn Storage>, collection: &str, id: &str) -> RenderedResponse {
+    println!("Received DELETE {}/{}", collection, id);
 
-    handle_400()
+    match storage.delete(collection, id).await {
+        Ok(_) => ApiResponse::ok(json!({ "deleted": true })).render(),
+        Err(e) => storage_error(&e, &format!("Failed to delete {}", singular(collection))).render(),
+    }
 }
 
-fn handle_400() -> String {
-    let status_line = "HTTP/1.0 400 BAD REQUEST";
-    let contents = "400 - Bad Request";
-    format!(
-        "{}\r\nContent-Length: {}\r\n\r\n{}",
-        status_line,
-        contents.len(),
-        contents
-    )
+fn singular(collection: &str) -> &str {
+    collection.strip_suffix('s').unwrap_or(collection)
 }
 
-fn handle_404() -> String {
-    let status_line = "HTTP/1.0 404 NOT FOUND";
-    let contents = "404 - Not Found";
-    format!(
-        "{}\r\nContent-Length: {}\r\n\r\n{}",
-        status_line,
-        contents.len(),
-        contents
-    )
+/// Maps a [`StorageError`] onto the `Failure`/`Fatal` arms of [`ApiResponse`].
+fn storage_error(error: &StorageError, fallback_message: &str) -> ApiResponse<Value> {
+    println!("Storage error: {}", error);
+
+    match error.kind {
+        ErrorKind::NotFound => ApiResponse::failure(404, error.message.clone()),
+        ErrorKind::BadRequest | ErrorKind::JsonParsing => ApiResponse::failure(400, error.message.clone()),
+        ErrorKind::Backend | ErrorKind::Other => ApiResponse::fatal(fallback_message.to_string()),
+    }
 }
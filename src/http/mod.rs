@@ -0,0 +1,241 @@
+//! Minimal HTTP/1.1 request reading and parsing.
+//!
+//! Replaces the old single `stream.read(&mut [0; 1024])` with a reader that
+//! accumulates bytes until the header terminator is found and then reads exactly
+//! `Content-Length` further bytes for the body, so requests aren't silently
+//! truncated and handlers no longer need to strip trailing `\0` padding.
+
+use std::collections::HashMap;
+use std::io::Read;
+
+mod response;
+mod router;
+
+pub use response::{ApiResponse, RenderedResponse};
+pub use router::{Dispatch, Handler, Params, Router};
+
+/// Default cap on request body size, used when the caller doesn't configure one.
+pub const DEFAULT_MAX_BODY_SIZE: usize = 10 * 1024 * 1024;
+
+/// Cap on the size of the request line plus headers, before `Content-Length` is
+/// even known. Independent of `max_body_size` since a client could otherwise
+/// send an unbounded header block while never completing the `\r\n\r\n`
+/// terminator.
+const MAX_HEADER_SIZE: usize = 8 * 1024;
+
+/// A parsed HTTP request.
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub query: HashMap<String, String>,
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    /// Returns the request body as UTF-8, replacing invalid sequences.
+    pub fn body_str(&self) -> std::borrow::Cow<'_, str> {
+        String::from_utf8_lossy(&self.body)
+    }
+}
+
+/// Why reading or parsing a request failed.
+#[derive(Debug)]
+pub enum RequestError {
+    /// The request line, headers, or `Content-Length` couldn't be parsed.
+    Malformed(String),
+    /// The header block or the `Content-Length` body exceeded the configured maximum.
+    TooLarge(usize),
+    /// The connection was closed or errored before a full request arrived.
+    Io(std::io::Error),
+}
+
+impl std::fmt::Display for RequestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RequestError::Malformed(msg) => write!(f, "malformed request: {}", msg),
+            RequestError::TooLarge(len) => write!(f, "request of {} bytes exceeds the configured size limit", len),
+            RequestError::Io(e) => write!(f, "failed to read request: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for RequestError {}
+
+impl From<std::io::Error> for RequestError {
+    fn from(e: std::io::Error) -> Self {
+        RequestError::Io(e)
+    }
+}
+
+/// Reads and parses one HTTP/1.1 request from `stream`.
+///
+/// Accumulates bytes until the `\r\n\r\n` header terminator is found, parses the
+/// request line, query string, and headers, then reads exactly `Content-Length`
+/// further bytes for the body. Requests without a body (or without a
+/// `Content-Length` header) are treated as having an empty body.
+pub fn read_request(stream: &mut impl Read, max_body_size: usize) -> Result<Request, RequestError> {
+    let mut buf = Vec::new();
+    let headers_end = loop {
+        if let Some(pos) = find_header_terminator(&buf) {
+            break pos;
+        }
+
+        if buf.len() >= MAX_HEADER_SIZE {
+            return Err(RequestError::TooLarge(buf.len()));
+        }
+
+        let mut chunk = [0u8; 1024];
+        let n = stream.read(&mut chunk)?;
+        if n == 0 {
+            return Err(RequestError::Malformed(
+                "connection closed before headers were complete".to_string(),
+            ));
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    };
+
+    let header_bytes = &buf[..headers_end];
+    let header_text = String::from_utf8_lossy(header_bytes);
+    let mut lines = header_text.split("\r\n");
+
+    let request_line = lines
+        .next()
+        .ok_or_else(|| RequestError::Malformed("missing request line".to_string()))?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts
+        .next()
+        .ok_or_else(|| RequestError::Malformed("missing method".to_string()))?
+        .to_string();
+    let raw_path = parts
+        .next()
+        .ok_or_else(|| RequestError::Malformed("missing path".to_string()))?;
+    let (path, query) = split_path_and_query(raw_path);
+
+    let mut headers = HashMap::new();
+    for line in lines {
+        if line.is_empty() {
+            continue;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_string());
+        }
+    }
+
+    let content_length = match headers.get("content-length") {
+        Some(value) => value
+            .parse::<usize>()
+            .map_err(|_| RequestError::Malformed(format!("invalid Content-Length: {}", value)))?,
+        None => 0,
+    };
+
+    if content_length > max_body_size {
+        return Err(RequestError::TooLarge(content_length));
+    }
+
+    let body_start = headers_end + 4;
+    let mut body = buf[body_start..].to_vec();
+
+    while body.len() < content_length {
+        let mut chunk = vec![0u8; content_length - body.len()];
+        let n = stream.read(&mut chunk)?;
+        if n == 0 {
+            return Err(RequestError::Malformed(
+                "connection closed before the full body arrived".to_string(),
+            ));
+        }
+        body.extend_from_slice(&chunk[..n]);
+    }
+    body.truncate(content_length);
+
+    Ok(Request {
+        method,
+        path,
+        query,
+        headers,
+        body,
+    })
+}
+
+fn find_header_terminator(buf: &[u8]) -> Option<usize> {
+    buf.windows(4).position(|window| window == b"\r\n\r\n")
+}
+
+fn split_path_and_query(raw_path: &str) -> (String, HashMap<String, String>) {
+    match raw_path.split_once('?') {
+        Some((path, query_string)) => (path.to_string(), parse_query_string(query_string)),
+        None => (raw_path.to_string(), HashMap::new()),
+    }
+}
+
+fn parse_query_string(query_string: &str) -> HashMap<String, String> {
+    query_string
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.split_once('=') {
+            Some((key, value)) => (key.to_string(), value.to_string()),
+            None => (pair.to_string(), String::new()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn reads_method_path_query_headers_and_body() {
+        let mut stream = Cursor::new(
+            b"POST /api/movies?sort=asc HTTP/1.1\r\nContent-Type: application/json\r\nContent-Length: 13\r\n\r\n{\"id\": \"42\"}\n"
+                .to_vec(),
+        );
+
+        let request = read_request(&mut stream, DEFAULT_MAX_BODY_SIZE).unwrap();
+
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/api/movies");
+        assert_eq!(request.query.get("sort"), Some(&"asc".to_string()));
+        assert_eq!(request.headers.get("content-type"), Some(&"application/json".to_string()));
+        assert_eq!(request.body_str(), "{\"id\": \"42\"}\n");
+    }
+
+    #[test]
+    fn missing_content_length_means_empty_body() {
+        let mut stream = Cursor::new(b"GET /api/movies HTTP/1.1\r\n\r\n".to_vec());
+
+        let request = read_request(&mut stream, DEFAULT_MAX_BODY_SIZE).unwrap();
+
+        assert!(request.body.is_empty());
+    }
+
+    #[test]
+    fn malformed_content_length_is_rejected() {
+        let mut stream = Cursor::new(b"GET / HTTP/1.1\r\nContent-Length: not-a-number\r\n\r\n".to_vec());
+
+        let err = read_request(&mut stream, DEFAULT_MAX_BODY_SIZE).unwrap_err();
+
+        assert!(matches!(err, RequestError::Malformed(_)));
+    }
+
+    #[test]
+    fn body_over_max_body_size_is_rejected() {
+        let mut stream = Cursor::new(b"POST / HTTP/1.1\r\nContent-Length: 100\r\n\r\n".to_vec());
+
+        let err = read_request(&mut stream, 10).unwrap_err();
+
+        assert!(matches!(err, RequestError::TooLarge(100)));
+    }
+
+    #[test]
+    fn oversized_header_block_is_rejected_before_content_length_is_known() {
+        let mut headers = b"GET / HTTP/1.1\r\n".to_vec();
+        headers.extend(std::iter::repeat(b'X').take(MAX_HEADER_SIZE + 1));
+        let mut stream = Cursor::new(headers);
+
+        let err = read_request(&mut stream, DEFAULT_MAX_BODY_SIZE).unwrap_err();
+
+        assert!(matches!(err, RequestError::TooLarge(_)));
+    }
+}
@@ -0,0 +1,160 @@
+//! A typed JSON response envelope so callers can distinguish a successful
+//! result from a recoverable validation failure from a backend outage, instead
+//! of parsing a bare status line and message string.
+
+use serde::Serialize;
+use serde_json::json;
+
+/// A tagged-union API response. Serializes to `{"type": "Success" | "Failure" |
+/// "Fatal", "content": ...}`.
+#[derive(Debug)]
+pub enum ApiResponse<T: Serialize> {
+    /// The request succeeded; `status` is usually 200 or 201.
+    Success { status: u16, content: T },
+    /// The caller's request was invalid or the resource wasn't found; `status`
+    /// is a 4xx code.
+    Failure { status: u16, content: String },
+    /// The backend or server failed; always reported as a 500.
+    Fatal { content: String },
+}
+
+/// A rendered HTTP response: the status code it was rendered with, alongside
+/// the full wire text. Keeps callers (e.g. metrics) from having to re-parse
+/// the status back out of the rendered string.
+#[derive(Debug, Clone)]
+pub struct RenderedResponse {
+    pub status: u16,
+    pub text: String,
+}
+
+impl<T: Serialize> ApiResponse<T> {
+    pub fn ok(content: T) -> Self {
+        ApiResponse::Success { status: 200, content }
+    }
+
+    pub fn created(content: T) -> Self {
+        ApiResponse::Success { status: 201, content }
+    }
+
+    pub fn failure(status: u16, message: impl Into<String>) -> Self {
+        ApiResponse::Failure {
+            status,
+            content: message.into(),
+        }
+    }
+
+    pub fn fatal(message: impl Into<String>) -> Self {
+        ApiResponse::Fatal {
+            content: message.into(),
+        }
+    }
+
+    /// Renders the status line, `Content-Type`, `Content-Length`, and JSON body
+    /// for this response, alongside the status code it was rendered with.
+    pub fn render(&self) -> RenderedResponse {
+        self.render_with_headers(&[])
+    }
+
+    /// Same as [`Self::render`], with additional response headers (e.g. `Allow`)
+    /// appended after `Content-Length`.
+    pub fn render_with_headers(&self, extra_headers: &[(&str, &str)]) -> RenderedResponse {
+        let (status, body) = match self {
+            ApiResponse::Success { status, content } => {
+                (*status, json!({"type": "Success", "content": content}))
+            }
+            ApiResponse::Failure { status, content } => {
+                (*status, json!({"type": "Failure", "content": content}))
+            }
+            ApiResponse::Fatal { content } => (500, json!({"type": "Fatal", "content": content})),
+        };
+
+        let body_text = body.to_string();
+        let mut headers = format!("Content-Type: application/json\r\nContent-Length: {}", body_text.len());
+        for (name, value) in extra_headers {
+            headers = format!("{}\r\n{}: {}", headers, name, value);
+        }
+
+        let text = format!("HTTP/1.0 {} {}\r\n{}\r\n\r\n{}", status, reason_phrase(status), headers, body_text);
+        RenderedResponse { status, text }
+    }
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "CREATED",
+        400 => "BAD REQUEST",
+        401 => "UNAUTHORIZED",
+        404 => "NOT FOUND",
+        405 => "METHOD NOT ALLOWED",
+        413 => "PAYLOAD TOO LARGE",
+        500 => "INTERNAL SERVER ERROR",
+        _ => "UNKNOWN",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    #[test]
+    fn ok_renders_200_success_envelope() {
+        let response = ApiResponse::ok(json!({"title": "Arrival"})).render();
+
+        assert_eq!(response.status, 200);
+        assert!(response.text.starts_with("HTTP/1.0 200 OK\r\n"));
+
+        let body = body_json(&response.text);
+        assert_eq!(body["type"], "Success");
+        assert_eq!(body["content"]["title"], "Arrival");
+    }
+
+    #[test]
+    fn created_renders_201_success_envelope() {
+        let response = ApiResponse::created(json!({"id": "1"})).render();
+
+        assert_eq!(response.status, 201);
+        assert!(response.text.starts_with("HTTP/1.0 201 CREATED\r\n"));
+    }
+
+    #[test]
+    fn failure_renders_its_own_status_with_failure_envelope() {
+        let response = ApiResponse::<Value>::failure(404, "movie not found").render();
+
+        assert_eq!(response.status, 404);
+        assert!(response.text.starts_with("HTTP/1.0 404 NOT FOUND\r\n"));
+
+        let body = body_json(&response.text);
+        assert_eq!(body["type"], "Failure");
+        assert_eq!(body["content"], "movie not found");
+    }
+
+    #[test]
+    fn fatal_always_renders_as_500_regardless_of_content() {
+        let response = ApiResponse::<Value>::fatal("backend unreachable").render();
+
+        assert_eq!(response.status, 500);
+        assert!(response.text.starts_with("HTTP/1.0 500 INTERNAL SERVER ERROR\r\n"));
+
+        let body = body_json(&response.text);
+        assert_eq!(body["type"], "Fatal");
+        assert_eq!(body["content"], "backend unreachable");
+    }
+
+    #[test]
+    fn render_with_headers_includes_extra_headers_alongside_content_length() {
+        let response = ApiResponse::<Value>::failure(405, "Method Not Allowed")
+            .render_with_headers(&[("Allow", "GET, POST")]);
+
+        let header_block = response.text.split("\r\n\r\n").next().unwrap();
+        assert!(header_block.contains("Content-Length:"));
+        assert!(header_block.contains("Allow: GET, POST"));
+    }
+
+    /// Parses the JSON body out of a rendered response's wire text.
+    fn body_json(text: &str) -> Value {
+        let body = text.split("\r\n\r\n").nth(1).unwrap();
+        serde_json::from_str(body).unwrap()
+    }
+}
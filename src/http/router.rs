@@ -0,0 +1,209 @@
+//! A small declarative router that matches `(method, path-pattern)` pairs and
+//! extracts `{name}`-style path segments into a params map, so resources can be
+//! addressed as `/api/movies/{id}` instead of smuggling the id through the body.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+
+use super::{RenderedResponse, Request};
+
+/// Path parameters bound while matching a route, e.g. `{"id": "42"}`.
+pub type Params = HashMap<String, String>;
+
+type BoxFuture = Pin<Box<dyn Future<Output = RenderedResponse> + Send>>;
+
+/// A boxed handler: takes the request and its bound path params, returns the
+/// rendered HTTP response.
+pub type Handler = Box<dyn Fn(Request, Params) -> BoxFuture + Send + Sync>;
+
+#[derive(Clone)]
+enum Segment {
+    Literal(String),
+    Param(String),
+}
+
+struct Route {
+    method: String,
+    pattern: String,
+    segments: Vec<Segment>,
+    handler: Handler,
+}
+
+/// The outcome of matching a request against the registered routes.
+pub enum Dispatch<'a> {
+    /// `route` is the registered pattern (e.g. `/api/movies/{id}`), not the
+    /// concrete request path, so callers can use it as a low-cardinality
+    /// metrics label.
+    Matched {
+        handler: &'a Handler,
+        params: Params,
+        route: &'a str,
+    },
+    MethodNotAllowed { allowed: Vec<String>, route: &'a str },
+    NotFound,
+}
+
+/// Registers `(method, path-pattern)` → handler routes and dispatches requests
+/// against them.
+#[derive(Default)]
+pub struct Router {
+    routes: Vec<Route>,
+}
+
+impl Router {
+    pub fn new() -> Self {
+        Router { routes: Vec::new() }
+    }
+
+    /// Registers a route. `pattern` segments wrapped in `{}` (e.g. `{id}`) bind
+    /// that path segment into the params map passed to `handler`.
+    pub fn route(&mut self, method: &str, pattern: &str, handler: Handler) -> &mut Self {
+        self.routes.push(Route {
+            method: method.to_string(),
+            pattern: pattern.to_string(),
+            segments: parse_pattern(pattern),
+            handler,
+        });
+        self
+    }
+
+    pub fn dispatch(&self, method: &str, path: &str) -> Dispatch<'_> {
+        let path_segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+        let mut allowed = Vec::new();
+        let mut matched_pattern = None;
+        for route in &self.routes {
+            let Some(params) = match_segments(&route.segments, &path_segments) else {
+                continue;
+            };
+
+            if route.method == method {
+                return Dispatch::Matched {
+                    handler: &route.handler,
+                    params,
+                    route: &route.pattern,
+                };
+            }
+            allowed.push(route.method.clone());
+            matched_pattern.get_or_insert(route.pattern.as_str());
+        }
+
+        match matched_pattern {
+            Some(route) => Dispatch::MethodNotAllowed { allowed, route },
+            None => Dispatch::NotFound,
+        }
+    }
+}
+
+fn parse_pattern(pattern: &str) -> Vec<Segment> {
+    pattern
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .map(|segment| match segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+            Some(name) => Segment::Param(name.to_string()),
+            None => Segment::Literal(segment.to_string()),
+        })
+        .collect()
+}
+
+fn match_segments(pattern: &[Segment], path: &[&str]) -> Option<Params> {
+    if pattern.len() != path.len() {
+        return None;
+    }
+
+    let mut params = HashMap::new();
+    for (segment, value) in pattern.iter().zip(path.iter()) {
+        match segment {
+            Segment::Literal(literal) => {
+                if literal != value {
+                    return None;
+                }
+            }
+            Segment::Param(name) => {
+                params.insert(name.clone(), value.to_string());
+            }
+        }
+    }
+
+    Some(params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn test_request(method: &str, path: &str) -> Request {
+        Request {
+            method: method.to_string(),
+            path: path.to_string(),
+            query: HashMap::new(),
+            headers: HashMap::new(),
+            body: Vec::new(),
+        }
+    }
+
+    fn echo_handler() -> Handler {
+        Box::new(|_req, _params| {
+            Box::pin(async {
+                RenderedResponse {
+                    status: 200,
+                    text: "ok".to_string(),
+                }
+            })
+        })
+    }
+
+    #[test]
+    fn matches_literal_route() {
+        let mut router = Router::new();
+        router.route("GET", "/api/movies", echo_handler());
+
+        match router.dispatch("GET", "/api/movies") {
+            Dispatch::Matched { route, params, .. } => {
+                assert_eq!(route, "/api/movies");
+                assert!(params.is_empty());
+            }
+            _ => panic!("expected a match"),
+        }
+    }
+
+    #[test]
+    fn binds_named_path_params() {
+        let mut router = Router::new();
+        router.route("GET", "/api/movies/{id}", echo_handler());
+
+        match router.dispatch("GET", "/api/movies/42") {
+            Dispatch::Matched { route, params, .. } => {
+                assert_eq!(route, "/api/movies/{id}");
+                assert_eq!(params.get("id"), Some(&"42".to_string()));
+            }
+            _ => panic!("expected a match"),
+        }
+    }
+
+    #[test]
+    fn unknown_path_is_not_found() {
+        let mut router = Router::new();
+        router.route("GET", "/api/movies", echo_handler());
+
+        assert!(matches!(router.dispatch("GET", "/api/actors"), Dispatch::NotFound));
+    }
+
+    #[test]
+    fn known_path_with_wrong_method_is_method_not_allowed() {
+        let mut router = Router::new();
+        router.route("GET", "/api/movies", echo_handler());
+        router.route("POST", "/api/movies", echo_handler());
+
+        match router.dispatch("DELETE", "/api/movies") {
+            Dispatch::MethodNotAllowed { mut allowed, route } => {
+                allowed.sort();
+                assert_eq!(allowed, vec!["GET".to_string(), "POST".to_string()]);
+                assert_eq!(route, "/api/movies");
+            }
+            _ => panic!("expected method not allowed"),
+        }
+    }
+}
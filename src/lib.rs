@@ -1,13 +1,26 @@
 use std::{
-    sync::{mpsc, Arc, Mutex},
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicBool, AtomicI64, Ordering},
+        mpsc, Arc, Mutex,
+    },
     thread,
 };
 
 use log::{error, info};
 
+pub mod auth;
+pub mod http;
+pub mod metrics;
+pub mod storage;
+
 pub struct ThreadPool {
-    workers: Vec<Worker>,
-    sender: mpsc::Sender<Message>,
+    workers: Mutex<Vec<Worker>>,
+    sender: Mutex<Option<mpsc::Sender<Message>>>,
+    shutting_down: AtomicBool,
+    queued_jobs: Arc<AtomicI64>,
+    active_workers: Arc<AtomicI64>,
 }
 
 type Job = Box<dyn FnOnce() + Send + 'static>;
@@ -29,40 +42,83 @@ impl ThreadPool {
         let (sender, receiver) = mpsc::channel();
 
         let receiver = Arc::new(Mutex::new(receiver));
+        let queued_jobs = Arc::new(AtomicI64::new(0));
+        let active_workers = Arc::new(AtomicI64::new(0));
 
         let mut workers = Vec::with_capacity(size);
 
         for id in 0..size {
-            workers.push(Worker::new(id, Arc::clone(&receiver)));
+            workers.push(Worker::new(
+                id,
+                Arc::clone(&receiver),
+                Arc::clone(&queued_jobs),
+                Arc::clone(&active_workers),
+            ));
         }
 
-        ThreadPool { workers, sender }
+        ThreadPool {
+            workers: Mutex::new(workers),
+            sender: Mutex::new(Some(sender)),
+            shutting_down: AtomicBool::new(false),
+            queued_jobs,
+            active_workers,
+        }
     }
 
+    /// Queues `f` to run on the next free worker. Jobs submitted after
+    /// [`Self::shutdown`] has been called are dropped.
     pub fn execute<F>(&self, f: F)
     where
         F: FnOnce() + Send + 'static,
     {
-        let job = Box::new(f);
-        if let Err(e) = self.sender.send(Message::NewJob(job)) {
-            error!("Failed to send job to the thread pool: {}", e);
+        if self.shutting_down.load(Ordering::SeqCst) {
+            error!("Rejected job: thread pool is shutting down");
+            return;
+        }
+
+        let job: Job = Box::new(f);
+        let sender = self.sender.lock().unwrap();
+        if let Some(sender) = sender.as_ref() {
+            self.queued_jobs.fetch_add(1, Ordering::SeqCst);
+            if let Err(e) = sender.send(Message::NewJob(job)) {
+                self.queued_jobs.fetch_sub(1, Ordering::SeqCst);
+                error!("Failed to send job to the thread pool: {}", e);
+            }
         }
     }
-}
 
-impl Drop for ThreadPool {
-    fn drop(&mut self) {
-        info!("Sending terminate message to all workers");
+    /// Number of jobs enqueued but not yet picked up by a worker.
+    pub fn queued_jobs(&self) -> i64 {
+        self.queued_jobs.load(Ordering::SeqCst)
+    }
 
-        for _ in &self.workers {
-            if let Err(e) = self.sender.send(Message::Terminate) {
-                error!("Failed to send terminate message to worker: {}", e);
+    /// Number of workers currently executing a job.
+    pub fn active_workers(&self) -> i64 {
+        self.active_workers.load(Ordering::SeqCst)
+    }
+
+    /// Stops accepting new jobs, lets already-queued jobs finish, then joins
+    /// every worker thread. Blocks until the pool is fully drained. Safe to
+    /// call more than once; later calls are no-ops.
+    pub fn shutdown(&self) {
+        if self.shutting_down.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        info!("Draining in-flight jobs before shutdown");
+
+        let worker_count = self.workers.lock().unwrap().len();
+        if let Some(sender) = self.sender.lock().unwrap().take() {
+            for _ in 0..worker_count {
+                if let Err(e) = sender.send(Message::Terminate) {
+                    error!("Failed to send terminate message to worker: {}", e);
+                }
             }
         }
 
         info!("Shutting down all workers");
 
-        for worker in &mut self.workers {
+        for worker in self.workers.lock().unwrap().iter_mut() {
             info!("Shutting down worker {}", worker.id);
 
             if let Some(thread) = worker.thread.take() {
@@ -74,20 +130,44 @@ impl Drop for ThreadPool {
     }
 }
 
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
 struct Worker {
     id: usize,
     thread: Option<thread::JoinHandle<()>>,
 }
 
 impl Worker {
-    fn new(id: usize, receiver: Arc<Mutex<mpsc::Receiver<Message>>>) -> Worker {
+    fn new(
+        id: usize,
+        receiver: Arc<Mutex<mpsc::Receiver<Message>>>,
+        queued_jobs: Arc<AtomicI64>,
+        active_workers: Arc<AtomicI64>,
+    ) -> Worker {
         let thread = thread::spawn(move || loop {
-            let message = receiver.lock().unwrap().recv().unwrap();
+            let message = match receiver.lock().unwrap().recv() {
+                Ok(message) => message,
+                Err(_) => {
+                    info!("Worker {} channel closed; exiting", id);
+                    break;
+                }
+            };
 
             match message {
                 Message::NewJob(job) => {
+                    queued_jobs.fetch_sub(1, Ordering::SeqCst);
+                    active_workers.fetch_add(1, Ordering::SeqCst);
+
                     info!("Worker {} got a new job; executing...", id);
-                    job();
+                    if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| job())) {
+                        error!("Worker {} job panicked: {}", id, panic_message(&payload));
+                    }
+
+                    active_workers.fetch_sub(1, Ordering::SeqCst);
                 }
                 Message::Terminate => {
                     info!("Worker {} is terminating", id);
@@ -102,3 +182,35 @@ impl Worker {
         }
     }
 }
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "non-string panic payload".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc::channel;
+    use std::time::Duration;
+
+    #[test]
+    fn worker_survives_a_panicking_job() {
+        let pool = ThreadPool::new(1);
+        let (tx, rx) = channel();
+
+        pool.execute(|| panic!("boom"));
+
+        pool.execute(move || {
+            tx.send(()).unwrap();
+        });
+
+        rx.recv_timeout(Duration::from_secs(2))
+            .expect("pool should still execute jobs after a panic");
+    }
+}
@@ -0,0 +1,118 @@
+//! A minimal Prometheus text-exposition-format metrics registry.
+//!
+//! Tracks per-route request counts and handler latency, and reads the
+//! [`ThreadPool`] saturation gauges at render time.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::ThreadPool;
+
+const LATENCY_BUCKETS_SECONDS: [f64; 7] = [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5];
+
+struct Histogram {
+    /// Cumulative counts per bucket boundary, matching Prometheus's `le` semantics.
+    buckets: [u64; LATENCY_BUCKETS_SECONDS.len()],
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn new() -> Self {
+        Histogram {
+            buckets: [0; LATENCY_BUCKETS_SECONDS.len()],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    fn observe(&mut self, seconds: f64) {
+        for (bucket, threshold) in self.buckets.iter_mut().zip(LATENCY_BUCKETS_SECONDS.iter()) {
+            if seconds <= *threshold {
+                *bucket += 1;
+            }
+        }
+        self.sum += seconds;
+        self.count += 1;
+    }
+}
+
+/// Records request counters and handler latency; rendered alongside thread
+/// pool saturation gauges as the `GET /metrics` response body.
+pub struct Metrics {
+    requests_total: Mutex<HashMap<(String, String, u16), u64>>,
+    handler_latency_seconds: Mutex<Histogram>,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Metrics {
+            requests_total: Mutex::new(HashMap::new()),
+            handler_latency_seconds: Mutex::new(Histogram::new()),
+        }
+    }
+
+    /// Records one dispatched request: its route pattern (e.g.
+    /// `/api/movies/{id}`, not the concrete path, to keep the counter's
+    /// cardinality bounded), the status it was rendered with, and how long
+    /// the handler took.
+    pub fn record_request(&self, method: &str, route: &str, status: u16, latency: Duration) {
+        let mut requests_total = self.requests_total.lock().unwrap();
+        *requests_total
+            .entry((method.to_string(), route.to_string(), status))
+            .or_insert(0) += 1;
+
+        self.handler_latency_seconds
+            .lock()
+            .unwrap()
+            .observe(latency.as_secs_f64());
+    }
+
+    /// Renders the Prometheus text exposition format, including the given
+    /// thread pool's saturation gauges.
+    pub fn render(&self, pool: &ThreadPool) -> String {
+        let mut out = String::new();
+
+        out.push_str("# HELP http_requests_total Total HTTP requests by method, route, and status.\n");
+        out.push_str("# TYPE http_requests_total counter\n");
+        for ((method, route, status), count) in self.requests_total.lock().unwrap().iter() {
+            out.push_str(&format!(
+                "http_requests_total{{method=\"{}\",route=\"{}\",status=\"{}\"}} {}\n",
+                method, route, status, count
+            ));
+        }
+
+        let histogram = self.handler_latency_seconds.lock().unwrap();
+        out.push_str("# HELP http_handler_latency_seconds Handler latency in seconds.\n");
+        out.push_str("# TYPE http_handler_latency_seconds histogram\n");
+        for (bucket, threshold) in histogram.buckets.iter().zip(LATENCY_BUCKETS_SECONDS.iter()) {
+            out.push_str(&format!(
+                "http_handler_latency_seconds_bucket{{le=\"{}\"}} {}\n",
+                threshold, bucket
+            ));
+        }
+        out.push_str(&format!(
+            "http_handler_latency_seconds_bucket{{le=\"+Inf\"}} {}\n",
+            histogram.count
+        ));
+        out.push_str(&format!("http_handler_latency_seconds_sum {}\n", histogram.sum));
+        out.push_str(&format!("http_handler_latency_seconds_count {}\n", histogram.count));
+
+        out.push_str("# HELP thread_pool_active_workers Workers currently executing a job.\n");
+        out.push_str("# TYPE thread_pool_active_workers gauge\n");
+        out.push_str(&format!("thread_pool_active_workers {}\n", pool.active_workers()));
+
+        out.push_str("# HELP thread_pool_queued_jobs Jobs queued but not yet picked up by a worker.\n");
+        out.push_str("# TYPE thread_pool_queued_jobs gauge\n");
+        out.push_str(&format!("thread_pool_queued_jobs {}\n", pool.queued_jobs()));
+
+        out
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
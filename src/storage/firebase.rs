@@ -0,0 +1,90 @@
+use firebase_rs::Firebase;
+use serde_json::Value;
+
+use super::{ErrorKind, Storage, StorageError};
+
+/// Storage backend that proxies every operation to a Firebase Realtime Database.
+pub struct FirebaseStorage {
+    firebase: Firebase,
+}
+
+impl FirebaseStorage {
+    pub fn new(url: &str) -> Result<Self, StorageError> {
+        let firebase = Firebase::new(url)
+            .map_err(|e| StorageError::new(ErrorKind::Backend, format!("invalid Firebase URL: {}", e)))?;
+        Ok(FirebaseStorage { firebase })
+    }
+}
+
+#[async_trait::async_trait]
+impl Storage for FirebaseStorage {
+    async fn list(&self, collection: &str) -> Result<Value, StorageError> {
+        let result = self
+            .firebase
+            .at(collection)
+            .get::<Value>()
+            .await
+            .map_err(|e| StorageError::backend(format!("failed to list {}: {:?}", collection, e)))?;
+
+        Ok(if result.is_null() { Value::Array(vec![]) } else { result })
+    }
+
+    async fn get(&self, collection: &str, id: &str) -> Result<Value, StorageError> {
+        let path = format!("{}/{}", collection, id);
+        let result = self
+            .firebase
+            .at(&path)
+            .get::<Value>()
+            .await
+            .map_err(|e| StorageError::backend(format!("failed to read {}: {:?}", path, e)))?;
+
+        if result.is_null() {
+            return Err(StorageError::not_found(format!("{} not found", path)));
+        }
+
+        Ok(result)
+    }
+
+    async fn create(&self, collection: &str, item: Value) -> Result<Value, StorageError> {
+        self.firebase
+            .at(collection)
+            .set(&item)
+            .await
+            .map_err(|e| StorageError::backend(format!("failed to create in {}: {:?}", collection, e)))?;
+
+        Ok(item)
+    }
+
+    async fn update(&self, collection: &str, id: &str, patch: Value) -> Result<Value, StorageError> {
+        let path = format!("{}/{}", collection, id);
+        self.firebase
+            .at(&path)
+            .update(&patch)
+            .await
+            .map_err(|e| StorageError::backend(format!("failed to update {}: {:?}", path, e)))?;
+
+        let merged = self
+            .firebase
+            .at(&path)
+            .get::<Value>()
+            .await
+            .map_err(|e| StorageError::backend(format!("failed to read {} after update: {:?}", path, e)))?;
+
+        if merged.is_null() {
+            return Err(StorageError::not_found(format!("{} not found", path)));
+        }
+
+        Ok(merged)
+    }
+
+    async fn delete(&self, collection: &str, id: &str) -> Result<(), StorageError> {
+        let path = format!("{}/{}", collection, id);
+        self.firebase
+            .at(&path)
+            .delete()
+            .await
+            .map_err(|e| StorageError::backend(format!("failed to delete {}: {:?}", path, e)))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,210 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use serde_json::Value;
+
+use super::{ErrorKind, Storage, StorageError};
+
+/// In-memory [`Storage`] backend, useful for tests and running the server without a
+/// real Firebase project. Each collection is a map of record id to JSON record.
+pub struct MemoryStorage {
+    collections: Mutex<HashMap<String, HashMap<String, Value>>>,
+    next_id: AtomicU64,
+}
+
+impl MemoryStorage {
+    pub fn new() -> Self {
+        MemoryStorage {
+            collections: Mutex::new(HashMap::new()),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    fn generate_id(&self) -> String {
+        self.next_id.fetch_add(1, Ordering::Relaxed).to_string()
+    }
+}
+
+impl Default for MemoryStorage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl Storage for MemoryStorage {
+    async fn list(&self, collection: &str) -> Result<Value, StorageError> {
+        let collections = self.collections.lock().unwrap();
+        let items = match collections.get(collection) {
+            Some(items) => items.values().cloned().collect(),
+            None => vec![],
+        };
+
+        Ok(Value::Array(items))
+    }
+
+    async fn get(&self, collection: &str, id: &str) -> Result<Value, StorageError> {
+        let collections = self.collections.lock().unwrap();
+        collections
+            .get(collection)
+            .and_then(|items| items.get(id))
+            .cloned()
+            .ok_or_else(|| StorageError::not_found(format!("{}/{} not found", collection, id)))
+    }
+
+    async fn create(&self, collection: &str, mut item: Value) -> Result<Value, StorageError> {
+        let id = match item.get("id").and_then(Value::as_str) {
+            Some(id) => id.to_string(),
+            None => self.generate_id(),
+        };
+
+        if let Value::Object(ref mut map) = item {
+            map.insert("id".to_string(), Value::String(id.clone()));
+        } else {
+            return Err(StorageError::bad_request("record must be a JSON object"));
+        }
+
+        let mut collections = self.collections.lock().unwrap();
+        collections
+            .entry(collection.to_string())
+            .or_default()
+            .insert(id, item.clone());
+
+        Ok(item)
+    }
+
+    async fn update(&self, collection: &str, id: &str, patch: Value) -> Result<Value, StorageError> {
+        let patch = patch
+            .as_object()
+            .ok_or_else(|| StorageError::bad_request("patch must be a JSON object"))?
+            .clone();
+
+        let mut collections = self.collections.lock().unwrap();
+        let items = collections
+            .get_mut(collection)
+            .ok_or_else(|| StorageError::not_found(format!("{} not found", collection)))?;
+
+        let existing = items
+            .get_mut(id)
+            .ok_or_else(|| StorageError::not_found(format!("{}/{} not found", collection, id)))?;
+
+        let existing_map = existing
+            .as_object_mut()
+            .ok_or_else(|| StorageError::new(ErrorKind::Other, "stored record is not a JSON object"))?;
+
+        for (key, value) in patch {
+            existing_map.insert(key, value);
+        }
+
+        Ok(existing.clone())
+    }
+
+    async fn delete(&self, collection: &str, id: &str) -> Result<(), StorageError> {
+        let mut collections = self.collections.lock().unwrap();
+        let items = collections
+            .get_mut(collection)
+            .ok_or_else(|| StorageError::not_found(format!("{} not found", collection)))?;
+
+        items
+            .remove(id)
+            .ok_or_else(|| StorageError::not_found(format!("{}/{} not found", collection, id)))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn create_then_list_and_get_round_trip() {
+        let storage = MemoryStorage::new();
+
+        let created = storage.create("movies", json!({"title": "Arrival"})).await.unwrap();
+        let id = created["id"].as_str().unwrap().to_string();
+
+        let fetched = storage.get("movies", &id).await.unwrap();
+        assert_eq!(fetched["title"], "Arrival");
+
+        let listed = storage.list("movies").await.unwrap();
+        assert_eq!(listed.as_array().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn list_on_unknown_collection_is_empty() {
+        let storage = MemoryStorage::new();
+        let listed = storage.list("movies").await.unwrap();
+        assert_eq!(listed, Value::Array(vec![]));
+    }
+
+    #[tokio::test]
+    async fn get_missing_record_is_not_found() {
+        let storage = MemoryStorage::new();
+        let err = storage.get("movies", "missing").await.unwrap_err();
+        assert_eq!(err.kind, ErrorKind::NotFound);
+    }
+
+    #[tokio::test]
+    async fn create_rejects_non_object_payload() {
+        let storage = MemoryStorage::new();
+        let err = storage.create("movies", json!("not an object")).await.unwrap_err();
+        assert_eq!(err.kind, ErrorKind::BadRequest);
+    }
+
+    #[tokio::test]
+    async fn update_merges_fields_into_existing_record() {
+        let storage = MemoryStorage::new();
+        let created = storage
+            .create("movies", json!({"title": "Arrival", "director": "Denis"}))
+            .await
+            .unwrap();
+        let id = created["id"].as_str().unwrap().to_string();
+
+        let updated = storage
+            .update("movies", &id, json!({"director": "Villeneuve"}))
+            .await
+            .unwrap();
+
+        assert_eq!(updated["title"], "Arrival");
+        assert_eq!(updated["director"], "Villeneuve");
+    }
+
+    #[tokio::test]
+    async fn update_missing_record_is_not_found() {
+        let storage = MemoryStorage::new();
+        let err = storage.update("movies", "missing", json!({})).await.unwrap_err();
+        assert_eq!(err.kind, ErrorKind::NotFound);
+    }
+
+    #[tokio::test]
+    async fn update_rejects_non_object_patch() {
+        let storage = MemoryStorage::new();
+        let created = storage.create("movies", json!({"title": "Arrival"})).await.unwrap();
+        let id = created["id"].as_str().unwrap().to_string();
+
+        let err = storage.update("movies", &id, json!("not an object")).await.unwrap_err();
+        assert_eq!(err.kind, ErrorKind::BadRequest);
+    }
+
+    #[tokio::test]
+    async fn delete_removes_record() {
+        let storage = MemoryStorage::new();
+        let created = storage.create("movies", json!({"title": "Arrival"})).await.unwrap();
+        let id = created["id"].as_str().unwrap().to_string();
+
+        storage.delete("movies", &id).await.unwrap();
+
+        let err = storage.get("movies", &id).await.unwrap_err();
+        assert_eq!(err.kind, ErrorKind::NotFound);
+    }
+
+    #[tokio::test]
+    async fn delete_missing_record_is_not_found() {
+        let storage = MemoryStorage::new();
+        let err = storage.delete("movies", "missing").await.unwrap_err();
+        assert_eq!(err.kind, ErrorKind::NotFound);
+    }
+}
@@ -0,0 +1,82 @@
+//! Pluggable storage backends for the collection resources (movies, actors, reviews).
+//!
+//! Handlers talk to a `dyn Storage` rather than a concrete database client, so the
+//! same dispatch code can run against Firebase in production and an in-memory
+//! backend in tests or local development.
+
+mod firebase;
+mod memory;
+
+use std::fmt;
+
+use serde_json::Value;
+
+pub use firebase::FirebaseStorage;
+pub use memory::MemoryStorage;
+
+/// What went wrong when a [`Storage`] operation failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The requested collection or record id does not exist.
+    NotFound,
+    /// The caller's input was malformed (e.g. missing required fields).
+    BadRequest,
+    /// The stored or incoming payload could not be parsed as JSON.
+    JsonParsing,
+    /// The underlying backend (database, network, etc.) reported a failure.
+    Backend,
+    /// Anything that doesn't fit the categories above.
+    Other,
+}
+
+/// An error returned by a [`Storage`] implementation.
+#[derive(Debug)]
+pub struct StorageError {
+    pub kind: ErrorKind,
+    pub message: String,
+}
+
+impl StorageError {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        StorageError {
+            kind,
+            message: message.into(),
+        }
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::NotFound, message)
+    }
+
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::BadRequest, message)
+    }
+
+    pub fn backend(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Backend, message)
+    }
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}: {}", self.kind, self.message)
+    }
+}
+
+impl std::error::Error for StorageError {}
+
+/// A CRUD-ish interface over a named collection of JSON records.
+///
+/// Records are addressed by collection name (`"movies"`, `"actors"`, `"reviews"`, ...)
+/// and record id. Implementations are expected to be cheap to clone/share via `Arc`
+/// and safe to call concurrently from multiple worker threads.
+#[async_trait::async_trait]
+pub trait Storage: Send + Sync {
+    async fn list(&self, collection: &str) -> Result<Value, StorageError>;
+    async fn get(&self, collection: &str, id: &str) -> Result<Value, StorageError>;
+    async fn create(&self, collection: &str, item: Value) -> Result<Value, StorageError>;
+    /// Merges `patch` into the record at `collection`/`id` and returns the
+    /// record's full state *after* the patch is applied, not just the patch.
+    async fn update(&self, collection: &str, id: &str, patch: Value) -> Result<Value, StorageError>;
+    async fn delete(&self, collection: &str, id: &str) -> Result<(), StorageError>;
+}